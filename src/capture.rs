@@ -0,0 +1,134 @@
+use std::ffi::CString;
+use std::os::raw::{c_char, c_int, c_void};
+
+use crate::{device::parse_device_list, sound_asset::SoundAsset, EzAl, SoundError};
+
+const ALC_CAPTURE_DEVICE_SPECIFIER: c_int = 0x310;
+const ALC_CAPTURE_SAMPLES: c_int = 0x312;
+
+const AL_FORMAT_MONO16: c_int = 0x1101;
+const AL_FORMAT_STEREO16: c_int = 0x1103;
+
+extern "C" {
+    fn alcCaptureOpenDevice(devicename: *const c_char, frequency: c_int, format: c_int, buffersize: c_int) -> *mut c_void;
+    fn alcCaptureCloseDevice(device: *mut c_void) -> c_char;
+    fn alcCaptureStart(device: *mut c_void);
+    fn alcCaptureStop(device: *mut c_void);
+    fn alcCaptureSamples(device: *mut c_void, buffer: *mut c_void, samples: c_int);
+    fn alcGetIntegerv(device: *mut c_void, param: c_int, size: c_int, data: *mut c_int);
+    fn alcGetString(device: *mut c_void, param: c_int) -> *const c_char;
+}
+
+/// Lists the names of every available capture (recording) device.
+pub fn list_capture_devices() -> Vec<String> {
+    unsafe {
+        let list = alcGetString(std::ptr::null_mut(), ALC_CAPTURE_DEVICE_SPECIFIER);
+        if list.is_null() {
+            return Vec::new();
+        }
+
+        parse_device_list(list)
+    }
+}
+
+/// An open ALC capture device, recording 16-bit PCM.
+pub struct CaptureDevice {
+    handle: *mut c_void,
+    channel_count: u16,
+    sample_rate: i32,
+}
+
+impl CaptureDevice {
+    /// Opens a capture device. Pass `None` to use the default capture device.
+    pub fn open(name: Option<&str>, sample_rate: i32, channel_count: u16, buffer_frames: i32) -> Result<Self, SoundError> {
+        let format = match channel_count {
+            1 => AL_FORMAT_MONO16,
+            2 => AL_FORMAT_STEREO16,
+            _ => return Err(SoundError::TooManyChannelsError),
+        };
+
+        let buffer_size = buffer_frames * channel_count as i32 * std::mem::size_of::<i16>() as i32;
+
+        let name_cstring = name.map(|name| CString::new(name).unwrap());
+        let name_ptr = match &name_cstring {
+            Some(name_cstring) => name_cstring.as_ptr(),
+            None => std::ptr::null(),
+        };
+
+        let handle = unsafe { alcCaptureOpenDevice(name_ptr, sample_rate, format, buffer_size) };
+        if handle.is_null() {
+            return Err(SoundError::CaptureDeviceError);
+        }
+
+        Ok(CaptureDevice { handle, channel_count, sample_rate })
+    }
+
+    pub fn start(&mut self) {
+        unsafe { alcCaptureStart(self.handle) };
+    }
+
+    pub fn stop(&mut self) {
+        unsafe { alcCaptureStop(self.handle) };
+    }
+
+    /// Number of whole sample frames currently available to read.
+    pub fn available_samples(&self) -> i32 {
+        let mut samples = 0;
+        unsafe { alcGetIntegerv(self.handle, ALC_CAPTURE_SAMPLES, 1, &mut samples) };
+        samples
+    }
+
+    /// Drains every currently available sample frame, appending interleaved `i16`s to `out`.
+    pub fn read_samples(&mut self, out: &mut Vec<i16>) {
+        let available = self.available_samples();
+        if available <= 0 {
+            return;
+        }
+
+        let start = out.len();
+        out.resize(start + available as usize * self.channel_count as usize, 0);
+
+        unsafe {
+            alcCaptureSamples(self.handle, out[start..].as_mut_ptr() as *mut c_void, available);
+        }
+    }
+
+    /// Builds a playable `SoundAsset` out of previously captured samples.
+    pub fn to_sound_asset(&self, al: &EzAl, samples: Vec<i16>) -> Result<SoundAsset, SoundError> {
+        SoundAsset::from_samples(al, samples, self.channel_count, self.sample_rate)
+    }
+
+    /// Writes previously captured samples out to a 16-bit PCM `.wav` file.
+    pub fn save_wav(&self, path: &str, samples: &[i16]) -> Result<(), SoundError> {
+        let spec = hound::WavSpec {
+            channels: self.channel_count,
+            sample_rate: self.sample_rate as u32,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+
+        let mut writer = match hound::WavWriter::create(path, spec) {
+            Ok(writer) => writer,
+            Err(_) => return Err(SoundError::SaveWavError),
+        };
+
+        for &sample in samples {
+            if writer.write_sample(sample).is_err() {
+                return Err(SoundError::SaveWavError);
+            }
+        }
+
+        match writer.finalize() {
+            Ok(()) => Ok(()),
+            Err(_) => Err(SoundError::SaveWavError),
+        }
+    }
+}
+
+impl Drop for CaptureDevice {
+    fn drop(&mut self) {
+        unsafe {
+            alcCaptureCloseDevice(self.handle);
+        }
+    }
+}
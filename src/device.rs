@@ -0,0 +1,47 @@
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_int, c_void};
+
+const ALC_DEVICE_SPECIFIER: c_int = 0x1005;
+const ALC_ALL_DEVICES_SPECIFIER: c_int = 0x1013;
+
+extern "C" {
+    fn alcGetString(device: *mut c_void, param: c_int) -> *const c_char;
+    fn alcIsExtensionPresent(device: *mut c_void, extname: *const c_char) -> c_char;
+}
+
+/// Lists the names of every available output device, most-specific enumeration first.
+pub fn list_output_devices() -> Vec<String> {
+    unsafe {
+        let extension_name = CString::new("ALC_ENUMERATE_ALL_EXT").unwrap();
+        let param = if alcIsExtensionPresent(std::ptr::null_mut(), extension_name.as_ptr()) != 0 {
+            ALC_ALL_DEVICES_SPECIFIER
+        } else {
+            ALC_DEVICE_SPECIFIER
+        };
+
+        let list = alcGetString(std::ptr::null_mut(), param);
+        if list.is_null() {
+            return Vec::new();
+        }
+
+        parse_device_list(list)
+    }
+}
+
+/// Parses ALC's null-separated, double-null-terminated device name list.
+pub(crate) unsafe fn parse_device_list(mut names: *const c_char) -> Vec<String> {
+    let mut devices = Vec::new();
+
+    loop {
+        let name = CStr::from_ptr(names);
+        let bytes = name.to_bytes();
+        if bytes.is_empty() {
+            break;
+        }
+
+        devices.push(name.to_string_lossy().into_owned());
+        names = names.add(bytes.len() + 1);
+    }
+
+    devices
+}
@@ -0,0 +1,292 @@
+use std::ffi::CString;
+use std::os::raw::{c_char, c_float, c_int, c_uint, c_void};
+
+use crate::{EzAl, SoundError};
+
+type ALuint = c_uint;
+type ALint = c_int;
+type ALfloat = c_float;
+type ALenum = c_int;
+type ALsizei = c_int;
+
+extern "C" {
+    fn alIsExtensionPresent(extname: *const c_char) -> c_char;
+    fn alGetProcAddress(fname: *const c_char) -> *mut c_void;
+    fn alSourcei(source: ALuint, param: ALenum, value: ALint);
+    fn alSource3i(source: ALuint, param: ALenum, value1: ALint, value2: ALint, value3: ALint);
+}
+
+type GenEffectsFn = unsafe extern "C" fn(ALsizei, *mut ALuint);
+type DeleteEffectsFn = unsafe extern "C" fn(ALsizei, *const ALuint);
+type EffectiFn = unsafe extern "C" fn(ALuint, ALenum, ALint);
+type EffectfFn = unsafe extern "C" fn(ALuint, ALenum, ALfloat);
+type GenAuxiliaryEffectSlotsFn = unsafe extern "C" fn(ALsizei, *mut ALuint);
+type DeleteAuxiliaryEffectSlotsFn = unsafe extern "C" fn(ALsizei, *const ALuint);
+type AuxiliaryEffectSlotiFn = unsafe extern "C" fn(ALuint, ALenum, ALint);
+type GenFiltersFn = unsafe extern "C" fn(ALsizei, *mut ALuint);
+type DeleteFiltersFn = unsafe extern "C" fn(ALsizei, *const ALuint);
+type FilteriFn = unsafe extern "C" fn(ALuint, ALenum, ALint);
+type FilterfFn = unsafe extern "C" fn(ALuint, ALenum, ALfloat);
+
+const AL_EFFECT_TYPE: ALenum = 0x8001;
+const AL_EFFECT_REVERB: ALenum = 0x0001;
+const AL_EFFECT_ECHO: ALenum = 0x0004;
+const AL_EFFECT_DISTORTION: ALenum = 0x0005;
+
+const AL_REVERB_DENSITY: ALenum = 0x0001;
+const AL_REVERB_DIFFUSION: ALenum = 0x0002;
+const AL_REVERB_GAIN: ALenum = 0x0003;
+const AL_REVERB_DECAY_TIME: ALenum = 0x0006;
+
+const AL_ECHO_DELAY: ALenum = 0x0001;
+const AL_ECHO_LRDELAY: ALenum = 0x0002;
+const AL_ECHO_DAMPING: ALenum = 0x0003;
+const AL_ECHO_FEEDBACK: ALenum = 0x0004;
+
+const AL_DISTORTION_EDGE: ALenum = 0x0001;
+const AL_DISTORTION_GAIN: ALenum = 0x0002;
+
+const AL_EFFECTSLOT_EFFECT: ALenum = 0x0001;
+
+const AL_FILTER_TYPE: ALenum = 0x8001;
+const AL_FILTER_LOWPASS: ALenum = 0x0001;
+const AL_LOWPASS_GAINHF: ALenum = 0x0002;
+
+const AL_DIRECT_FILTER: ALenum = 0x20005;
+const AL_AUXILIARY_SEND_FILTER: ALenum = 0x20006;
+const AL_FILTER_NULL: ALint = 0;
+
+/// Loaded `ALC_EXT_EFX` function pointers, owned by the `EzAl` that loaded them. Storing this on
+/// `EzAl` instead of behind a global means multiple `EzAl` instances (and threads creating them
+/// concurrently) each get their own table instead of racing on a shared one.
+#[derive(Clone, Copy)]
+pub(crate) struct EfxFunctions {
+    gen_effects: GenEffectsFn,
+    delete_effects: DeleteEffectsFn,
+    effecti: EffectiFn,
+    effectf: EffectfFn,
+    gen_aux_effect_slots: GenAuxiliaryEffectSlotsFn,
+    delete_aux_effect_slots: DeleteAuxiliaryEffectSlotsFn,
+    aux_effect_sloti: AuxiliaryEffectSlotiFn,
+    gen_filters: GenFiltersFn,
+    delete_filters: DeleteFiltersFn,
+    filteri: FilteriFn,
+    filterf: FilterfFn,
+}
+
+/// Attempts to load the `ALC_EXT_EFX` extension functions. Should be called once, right after
+/// the context is made current. Returns `SoundError::EfxUnsupported` if the extension, or any of
+/// the functions it exposes, isn't available on this device.
+pub(crate) fn init() -> Result<EfxFunctions, SoundError> {
+    unsafe {
+        let extension_name = CString::new("ALC_EXT_EFX").unwrap();
+        if alIsExtensionPresent(extension_name.as_ptr()) == 0 {
+            return Err(SoundError::EfxUnsupported);
+        }
+
+        macro_rules! load {
+            ($name:expr) => {{
+                let proc_name = CString::new($name).unwrap();
+                let proc_address = alGetProcAddress(proc_name.as_ptr());
+                if proc_address.is_null() {
+                    return Err(SoundError::EfxUnsupported);
+                }
+                std::mem::transmute(proc_address)
+            }};
+        }
+
+        Ok(EfxFunctions {
+            gen_effects: load!("alGenEffects"),
+            delete_effects: load!("alDeleteEffects"),
+            effecti: load!("alEffecti"),
+            effectf: load!("alEffectf"),
+            gen_aux_effect_slots: load!("alGenAuxiliaryEffectSlots"),
+            delete_aux_effect_slots: load!("alDeleteAuxiliaryEffectSlots"),
+            aux_effect_sloti: load!("alAuxiliaryEffectSloti"),
+            gen_filters: load!("alGenFilters"),
+            delete_filters: load!("alDeleteFilters"),
+            filteri: load!("alFilteri"),
+            filterf: load!("alFilterf"),
+        })
+    }
+}
+
+fn functions(al: &EzAl) -> Result<&EfxFunctions, SoundError> {
+    match &al.efx {
+        Some(functions) => Ok(functions),
+        None => Err(SoundError::EfxUnsupported),
+    }
+}
+
+/// A reverb preset, configuring density/diffusion/decay/gain on the underlying reverb effect.
+#[derive(Debug, Clone, Copy)]
+pub enum ReverbPreset {
+    Generic,
+    Cave,
+    Hangar,
+    Underwater,
+}
+
+impl ReverbPreset {
+    /// Returns (density, diffusion, decay_time, gain).
+    fn params(self) -> (f32, f32, f32, f32) {
+        match self {
+            ReverbPreset::Generic => (1.0, 1.0, 1.49, 0.32),
+            ReverbPreset::Cave => (1.0, 1.0, 2.91, 0.5),
+            ReverbPreset::Hangar => (1.0, 1.0, 10.05, 0.32),
+            ReverbPreset::Underwater => (0.3645, 1.0, 1.499, 0.25),
+        }
+    }
+}
+
+/// An effect that can be attached to an `EffectSlot`.
+#[derive(Debug, Clone, Copy)]
+pub enum Effect {
+    /// Simulates the acoustics of an enclosed space.
+    Reverb(ReverbPreset),
+    /// A delayed repeat of the source's output.
+    Echo { delay: f32, feedback: f32 },
+    /// Clips and distorts the source's output.
+    Distortion { edge: f32, gain: f32 },
+}
+
+/// An auxiliary effect slot with an `Effect` attached.
+///
+/// Route a `SoundSource` into it with `SoundSource::set_aux_send`.
+pub struct EffectSlot {
+    pub(crate) slot_id: ALuint,
+    effect_id: ALuint,
+    delete_aux_effect_slots: DeleteAuxiliaryEffectSlotsFn,
+    delete_effects: DeleteEffectsFn,
+}
+
+impl EffectSlot {
+    /// Creates a new auxiliary effect slot configured with `effect`.
+    pub fn new(al: &EzAl, effect: Effect) -> Result<Self, SoundError> {
+        let functions = match functions(al) {
+            Ok(functions) => functions,
+            Err(err) => return Err(err),
+        };
+
+        let mut effect_id: ALuint = 0;
+        let mut slot_id: ALuint = 0;
+        unsafe {
+            (functions.gen_effects)(1, &mut effect_id);
+
+            match effect {
+                Effect::Reverb(preset) => {
+                    (functions.effecti)(effect_id, AL_EFFECT_TYPE, AL_EFFECT_REVERB);
+
+                    let (density, diffusion, decay_time, gain) = preset.params();
+                    (functions.effectf)(effect_id, AL_REVERB_DENSITY, density);
+                    (functions.effectf)(effect_id, AL_REVERB_DIFFUSION, diffusion);
+                    (functions.effectf)(effect_id, AL_REVERB_DECAY_TIME, decay_time);
+                    (functions.effectf)(effect_id, AL_REVERB_GAIN, gain);
+                }
+                Effect::Echo { delay, feedback } => {
+                    (functions.effecti)(effect_id, AL_EFFECT_TYPE, AL_EFFECT_ECHO);
+                    (functions.effectf)(effect_id, AL_ECHO_DELAY, delay);
+                    (functions.effectf)(effect_id, AL_ECHO_LRDELAY, delay);
+                    (functions.effectf)(effect_id, AL_ECHO_DAMPING, 0.5);
+                    (functions.effectf)(effect_id, AL_ECHO_FEEDBACK, feedback);
+                }
+                Effect::Distortion { edge, gain } => {
+                    (functions.effecti)(effect_id, AL_EFFECT_TYPE, AL_EFFECT_DISTORTION);
+                    (functions.effectf)(effect_id, AL_DISTORTION_EDGE, edge);
+                    (functions.effectf)(effect_id, AL_DISTORTION_GAIN, gain);
+                }
+            }
+
+            (functions.gen_aux_effect_slots)(1, &mut slot_id);
+            (functions.aux_effect_sloti)(slot_id, AL_EFFECTSLOT_EFFECT, effect_id as ALint);
+        }
+
+        Ok(EffectSlot {
+            slot_id,
+            effect_id,
+            delete_aux_effect_slots: functions.delete_aux_effect_slots,
+            delete_effects: functions.delete_effects,
+        })
+    }
+
+    /// Creates a new auxiliary effect slot configured with the given reverb preset.
+    ///
+    /// Shorthand for `EffectSlot::new(al, Effect::Reverb(preset))`.
+    pub fn new_reverb(al: &EzAl, preset: ReverbPreset) -> Result<Self, SoundError> {
+        Self::new(al, Effect::Reverb(preset))
+    }
+}
+
+impl Drop for EffectSlot {
+    fn drop(&mut self) {
+        unsafe {
+            (self.delete_aux_effect_slots)(1, &self.slot_id);
+            (self.delete_effects)(1, &self.effect_id);
+        }
+    }
+}
+
+/// A low-pass filter, useful for muffling a source's direct (non-reverb) output to simulate
+/// occlusion.
+pub struct DirectFilter {
+    pub(crate) filter_id: ALuint,
+    delete_filters: DeleteFiltersFn,
+}
+
+impl DirectFilter {
+    /// Creates a new low-pass filter. `gain_hf` attenuates high frequencies; 1.0 leaves the
+    /// sound untouched, lower values muffle it more.
+    pub fn new_low_pass(al: &EzAl, gain_hf: f32) -> Result<Self, SoundError> {
+        let functions = match functions(al) {
+            Ok(functions) => functions,
+            Err(err) => return Err(err),
+        };
+
+        let mut filter_id: ALuint = 0;
+        unsafe {
+            (functions.gen_filters)(1, &mut filter_id);
+            (functions.filteri)(filter_id, AL_FILTER_TYPE, AL_FILTER_LOWPASS);
+            (functions.filterf)(filter_id, AL_LOWPASS_GAINHF, gain_hf);
+        }
+
+        Ok(DirectFilter { filter_id, delete_filters: functions.delete_filters })
+    }
+}
+
+impl Drop for DirectFilter {
+    fn drop(&mut self) {
+        unsafe {
+            (self.delete_filters)(1, &self.filter_id);
+        }
+    }
+}
+
+/// Alias for `DirectFilter` under the name used when routing to an auxiliary send via
+/// `SoundSource::set_aux_send`.
+pub type Filter = DirectFilter;
+
+/// Routes a source's output through an auxiliary effect slot, optionally via a filter.
+pub(crate) fn route_to_aux_send(al: &EzAl, source_id: u32, slot_id: ALuint, filter_id: ALuint) -> Result<(), SoundError> {
+    if let Err(err) = functions(al) {
+        return Err(err);
+    }
+
+    unsafe {
+        alSource3i(source_id, AL_AUXILIARY_SEND_FILTER, slot_id as ALint, 0, filter_id as ALint);
+    }
+
+    Ok(())
+}
+
+/// Applies a direct (non-reverb) filter to a source, e.g. for occlusion.
+pub(crate) fn apply_direct_filter(al: &EzAl, source_id: u32, filter_id: ALuint) -> Result<(), SoundError> {
+    if let Err(err) = functions(al) {
+        return Err(err);
+    }
+
+    unsafe {
+        alSourcei(source_id, AL_DIRECT_FILTER, filter_id as ALint);
+    }
+
+    Ok(())
+}
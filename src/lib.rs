@@ -1,75 +1,138 @@
 use allen::{AllenError, Context, Device, Orientation};
+use allen::DistanceModel as AllenDistanceModel;
 
+pub mod capture;
+pub mod device;
+pub mod efx;
 pub mod sound_asset;
 pub mod sound_source;
+pub mod streaming_source;
+
+pub use capture::CaptureDevice;
+pub use device::list_output_devices;
+pub use efx::{DirectFilter, Effect, EffectSlot, Filter, ReverbPreset};
+pub use sound_asset::{SoundAsset, WavAsset};
+pub use sound_source::{PlaybackState, SoundSource, SoundSourceType};
+pub use streaming_source::StreamingSource;
 
 #[derive(Debug)]
 pub enum SoundError {
     CurrentDeviceGettingError,
     ContextCreationError(AllenError),
     Not16BitWavFileError,
-    NotMonoWavFileError,
+    TooManyChannelsError,
     SoundAssetLoadingError,
     BufferCreationFailedError(AllenError),
     SourceCreationFailedError(AllenError),
     WrongEmitterType,
-    SettingPositionError(AllenError)
+    SettingPositionError(AllenError),
+    /// The `ALC_EXT_EFX` extension (or one of the functions it exposes) isn't available on this device
+    EfxUnsupported,
+    /// No output device enumerates with the name passed to `EzAl::new_with_device`
+    DeviceNotFound(String),
+    /// Failed to open a capture (recording) device
+    CaptureDeviceError,
+    /// Failed to write a `.wav` file
+    SaveWavError,
 }
 
-static mut DEVICE: Option<Device> = None;
-pub static mut CONTEXT: Option<Context> = None;
+/// Owns the open output device and current OpenAL context. Every sound-creating or
+/// listener-affecting call takes a `&EzAl` so they can't accidentally run before audio is
+/// initialized or against the wrong context.
+pub struct EzAl {
+    pub(crate) context: Context,
+    _device: Device,
+    pub(crate) efx: Option<efx::EfxFunctions>,
+}
 
-pub fn init() -> Result<(), SoundError> {
-    unsafe {
-        let device = Device::open(None);
-        match device {
-            None => {
-                return Err(SoundError::CurrentDeviceGettingError);
-            }
-            Some(_) => (),
+impl EzAl {
+    /// Initializes audio using the default output device.
+    pub fn new() -> Result<Self, SoundError> {
+        Self::new_internal(None)
+    }
+
+    /// Initializes audio using the named output device.
+    ///
+    /// `name` must be one of the names returned by `device::list_output_devices()`.
+    pub fn new_with_device(name: &str) -> Result<Self, SoundError> {
+        if !device::list_output_devices().iter().any(|device_name| device_name == name) {
+            return Err(SoundError::DeviceNotFound(name.to_string()));
         }
-        let device = device.unwrap();
-        let context = device.create_context();
-        match context {
-            Err(err) => {
-                return Err(SoundError::ContextCreationError(err));
+
+        Self::new_internal(Some(name))
+    }
+
+    fn new_internal(name: Option<&str>) -> Result<Self, SoundError> {
+        unsafe {
+            let device = Device::open(name);
+            match device {
+                None => {
+                    return Err(SoundError::CurrentDeviceGettingError);
+                }
+                Some(_) => (),
             }
-            Ok(_) => (),
-        }
-        DEVICE = Some(device);
+            let device = device.unwrap();
+            let context = device.create_context();
+            match context {
+                Err(err) => {
+                    return Err(SoundError::ContextCreationError(err));
+                }
+                Ok(_) => (),
+            }
+            let context = context.unwrap();
+            context.make_current();
 
-        let context = context.unwrap();
-        context.make_current();
-        CONTEXT = Some(context);
+            // EFX is an optional extension; ignore failures here and let callers find out when
+            // they try to create an EffectSlot
+            let efx = efx::init().ok();
 
-        return Ok(());
+            Ok(EzAl { context, _device: device, efx })
+        }
     }
 }
 
-pub fn set_listener_position(position: [f32; 3]) {
-    let context = take_context();
-    let _ = context.listener().set_position(position);
-    return_context(context)
+pub fn set_listener_position(al: &EzAl, position: [f32; 3]) {
+    let _ = al.context.listener().set_position(position);
 }
 
-pub fn set_listener_orientation(at: [f32; 3], up: [f32; 3]) {
-    let context = take_context();
-    let _ = context.listener().set_orientation(Orientation { at, up });
-    return_context(context)
+pub fn set_listener_orientation(al: &EzAl, at: [f32; 3], up: [f32; 3]) {
+    let _ = al.context.listener().set_orientation(Orientation { at, up });
 }
 
-pub fn set_listener_transform(position: [f32; 3], at: [f32; 3], up: [f32; 3]) {
-    set_listener_position(position);
-    set_listener_orientation(at, up);
+pub fn set_listener_transform(al: &EzAl, position: [f32; 3], at: [f32; 3], up: [f32; 3]) {
+    set_listener_position(al, position);
+    set_listener_orientation(al, at, up);
 }
 
+/// Attenuation curve used to compute how a source's volume drops off with distance.
+///
+/// See the OpenAL specification for the exact formula each variant uses.
+#[derive(Debug, Clone, Copy)]
+pub enum DistanceModel {
+    None,
+    InverseDistance,
+    InverseDistanceClamped,
+    LinearDistance,
+    LinearDistanceClamped,
+    ExponentDistance,
+    ExponentDistanceClamped,
+}
 
-pub fn take_context() -> Context {
-    unsafe {
-        return CONTEXT.take().unwrap();
+impl From<DistanceModel> for AllenDistanceModel {
+    fn from(model: DistanceModel) -> Self {
+        match model {
+            DistanceModel::None => AllenDistanceModel::None,
+            DistanceModel::InverseDistance => AllenDistanceModel::InverseDistance,
+            DistanceModel::InverseDistanceClamped => AllenDistanceModel::InverseDistanceClamped,
+            DistanceModel::LinearDistance => AllenDistanceModel::LinearDistance,
+            DistanceModel::LinearDistanceClamped => AllenDistanceModel::LinearDistanceClamped,
+            DistanceModel::ExponentDistance => AllenDistanceModel::ExponentDistance,
+            DistanceModel::ExponentDistanceClamped => AllenDistanceModel::ExponentDistanceClamped,
+        }
     }
 }
 
-pub fn return_context(context: Context) {
-    unsafe { CONTEXT = Some(context) }
+/// Sets the distance attenuation model used for every positional source.
+pub fn set_distance_model(al: &EzAl, model: DistanceModel) {
+    let _ = al.context.set_distance_model(model.into());
 }
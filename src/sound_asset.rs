@@ -1,34 +1,57 @@
+use std::path::Path;
+
 use allen::{Buffer, BufferData, Channels};
+use claxon::FlacReader;
 use hound::WavReader;
+use lewton::inside_ogg::OggStreamReader;
+use minimp3::{Decoder, Error as Mp3Error};
 
-use crate::{SoundError, take_context, return_context};
+use crate::{EzAl, SoundError};
 
-pub struct WavAsset {
+pub struct SoundAsset {
     samples: Vec<i16>,
     pub buffer: Buffer,
+    pub channel_count: u16,
+    /// A mono downmix of `buffer`, present only for stereo assets, so they can still be used
+    /// with `SoundSourceType::Positional` (which requires a mono buffer).
+    pub(crate) mono_buffer: Option<Buffer>,
 }
 
-impl WavAsset {
-    pub fn from_wav(path: &str) -> Result<Self, SoundError> {
-        let context = take_context();
+/// Old name for `SoundAsset`, kept so existing code doesn't break.
+pub type WavAsset = SoundAsset;
+
+impl SoundAsset {
+    /// Loads an asset, picking a decoder based on the file extension (wav/ogg/flac/mp3).
+    pub fn from_file(al: &EzAl, path: &str) -> Result<Self, SoundError> {
+        let extension = Path::new(path)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_lowercase());
+
+        match extension.as_deref() {
+            Some("wav") => Self::from_wav(al, path),
+            Some("ogg") => Self::from_ogg(al, path),
+            Some("flac") => Self::from_flac(al, path),
+            Some("mp3") => Self::from_mp3(al, path),
+            _ => Err(SoundError::SoundAssetLoadingError),
+        }
+    }
 
+    pub fn from_wav(al: &EzAl, path: &str) -> Result<Self, SoundError> {
         let reader = WavReader::open(path);
         match reader {
             Ok(_) => (),
-            Err(err) => {
-                return_context(context);
+            Err(_) => {
                 return Err(SoundError::SoundAssetLoadingError);
             }
         }
         let mut reader = reader.unwrap();
 
-        if reader.spec().channels > 1 {
-            return_context(context);
-            return Err(SoundError::NotMonoWavFileError);
+        if reader.spec().channels > 2 {
+            return Err(SoundError::TooManyChannelsError);
         }
 
         if reader.spec().bits_per_sample != 16 {
-            return_context(context);
             return Err(SoundError::Not16BitWavFileError);
         }
 
@@ -37,31 +60,148 @@ impl WavAsset {
             .map(|s| s.unwrap())
             .collect::<Vec<_>>();
 
+        Self::from_samples(al, samples, reader.spec().channels, reader.spec().sample_rate as i32)
+    }
+
+    pub fn from_ogg(al: &EzAl, path: &str) -> Result<Self, SoundError> {
+        let file = match std::fs::File::open(path) {
+            Ok(file) => file,
+            Err(_) => return Err(SoundError::SoundAssetLoadingError),
+        };
+
+        let reader = OggStreamReader::new(file);
+        match reader {
+            Ok(_) => (),
+            Err(_) => {
+                return Err(SoundError::SoundAssetLoadingError);
+            }
+        }
+        let mut reader = reader.unwrap();
+
+        if reader.ident_hdr.audio_channels > 2 {
+            return Err(SoundError::TooManyChannelsError);
+        }
+
+        let channel_count = reader.ident_hdr.audio_channels as u16;
+        let sample_rate = reader.ident_hdr.audio_sample_rate as i32;
+        let mut samples = Vec::new();
+
+        loop {
+            match reader.read_dec_packet_itl() {
+                Ok(Some(mut packet)) => samples.append(&mut packet),
+                Ok(None) => break,
+                Err(_) => return Err(SoundError::SoundAssetLoadingError),
+            }
+        }
+
+        Self::from_samples(al, samples, channel_count, sample_rate)
+    }
+
+    pub fn from_flac(al: &EzAl, path: &str) -> Result<Self, SoundError> {
+        let reader = FlacReader::open(path);
+        match reader {
+            Ok(_) => (),
+            Err(_) => {
+                return Err(SoundError::SoundAssetLoadingError);
+            }
+        }
+        let mut reader = reader.unwrap();
+
+        if reader.streaminfo().channels > 2 {
+            return Err(SoundError::TooManyChannelsError);
+        }
+
+        let channel_count = reader.streaminfo().channels as u16;
+        let sample_rate = reader.streaminfo().sample_rate as i32;
+        // claxon yields samples scaled to the file's actual bit depth, not always 16-bit, so
+        // rescale down to 16-bit instead of truncating (which would just keep the low bits and
+        // produce loud, garbled noise for anything encoded above 16-bit).
+        let shift = reader.streaminfo().bits_per_sample.saturating_sub(16);
+        let mut samples = Vec::new();
+        for sample in reader.samples() {
+            match sample {
+                Ok(sample) => samples.push((sample >> shift) as i16),
+                Err(_) => return Err(SoundError::SoundAssetLoadingError),
+            }
+        }
+
+        Self::from_samples(al, samples, channel_count, sample_rate)
+    }
+
+    pub fn from_mp3(al: &EzAl, path: &str) -> Result<Self, SoundError> {
+        let file = match std::fs::File::open(path) {
+            Ok(file) => file,
+            Err(_) => return Err(SoundError::SoundAssetLoadingError),
+        };
+
+        let mut decoder = Decoder::new(file);
+        let mut samples = Vec::new();
+        let mut sample_rate = 0;
+        let mut channel_count = 1;
+
+        loop {
+            match decoder.next_frame() {
+                Ok(frame) => {
+                    if frame.channels > 2 {
+                        return Err(SoundError::TooManyChannelsError);
+                    }
+
+                    channel_count = frame.channels as u16;
+                    sample_rate = frame.sample_rate;
+                    samples.extend_from_slice(&frame.data);
+                }
+                Err(Mp3Error::Eof) => break,
+                Err(_) => return Err(SoundError::SoundAssetLoadingError),
+            }
+        }
+
+        Self::from_samples(al, samples, channel_count, sample_rate)
+    }
+
+    pub(crate) fn from_samples(al: &EzAl, samples: Vec<i16>, channel_count: u16, sample_rate: i32) -> Result<Self, SoundError> {
+        let channels = match channel_count {
+            1 => Channels::Mono,
+            2 => Channels::Stereo,
+            _ => return Err(SoundError::TooManyChannelsError),
+        };
+
+        let context = &al.context;
+
         let buffer = context.new_buffer();
         match buffer {
             Ok(_) => (),
             Err(err) => {
-                return_context(context);
                 return Err(SoundError::BufferCreationFailedError(err));
             }
         }
         let buffer = buffer.unwrap();
 
-        match buffer.data(
-            BufferData::I16(&samples),
-            Channels::Mono,
-            reader.spec().sample_rate as i32,
-        ) {
-            Ok(_) => (),
-            Err(err) => {
-                return_context(context);
-                return Err(SoundError::BufferCreationFailedError(err));
-            }
+        if let Err(err) = buffer.data(BufferData::I16(&samples), channels, sample_rate) {
+            return Err(SoundError::BufferCreationFailedError(err));
         };
 
-        return_context(context);
+        let mono_buffer = match channels {
+            Channels::Mono => None,
+            Channels::Stereo => {
+                let mono_buffer = context.new_buffer();
+                match mono_buffer {
+                    Ok(_) => (),
+                    Err(err) => {
+                        return Err(SoundError::BufferCreationFailedError(err));
+                    }
+                }
+                let mono_buffer = mono_buffer.unwrap();
 
-        return Ok(WavAsset { samples, buffer });
+                // Naive downmix: keep every other (left-channel) sample.
+                let mono_samples: Vec<i16> = samples.iter().step_by(2).copied().collect();
+                if let Err(err) = mono_buffer.data(BufferData::I16(&mono_samples), Channels::Mono, sample_rate) {
+                    return Err(SoundError::BufferCreationFailedError(err));
+                };
+
+                Some(mono_buffer)
+            }
+        };
+
+        Ok(SoundAsset { samples, buffer, channel_count, mono_buffer })
     }
 }
-
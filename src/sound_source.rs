@@ -1,10 +1,12 @@
 use std::fmt::Debug;
-use allen::Source;
-use crate::{sound_asset::WavAsset, SoundError, take_context, return_context};
+use allen::{AllenError, Source, SourceState};
+use crate::{efx, sound_asset::SoundAsset, EzAl, SoundError};
 
 pub struct SoundSource {
     pub emitter_type: SoundSourceType,
     source: Source,
+    last_state: PlaybackState,
+    on_end: Option<Box<dyn FnMut()>>,
 }
 
 #[derive(Debug)]
@@ -13,34 +15,63 @@ pub enum SoundSourceType {
     Positional,
 }
 
+/// Playback state of a SoundSource, mirroring OpenAL's source state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlaybackState {
+    Initial,
+    Playing,
+    Paused,
+    Stopped,
+}
+
+impl From<SourceState> for PlaybackState {
+    fn from(state: SourceState) -> Self {
+        match state {
+            SourceState::Initial => PlaybackState::Initial,
+            SourceState::Playing => PlaybackState::Playing,
+            SourceState::Paused => PlaybackState::Paused,
+            SourceState::Stopped => PlaybackState::Stopped,
+        }
+    }
+}
+
 impl SoundSource {
-    pub fn new(asset: &WavAsset, emitter_type: SoundSourceType) -> Result<SoundSource, SoundError> {
-        let context = take_context();
+    pub fn new(al: &EzAl, asset: &SoundAsset, emitter_type: SoundSourceType) -> Result<SoundSource, SoundError> {
+        let context = &al.context;
         let source_result = context.new_source();
         let source: Source;
         match source_result {
             Ok(src) => source = src,
             Err(err) => {
-                return_context(context);
                 return Err(SoundError::SourceCreationFailedError(err));
             }
         }
 
-        let _ = source.set_buffer(Some(&asset.buffer));
         match emitter_type {
-            SoundSourceType::Simple => source.set_relative(true).unwrap(),
+            SoundSourceType::Simple => {
+                source.set_relative(true).unwrap();
+                let _ = source.set_buffer(Some(&asset.buffer));
+            }
             SoundSourceType::Positional => {
+                // Positional (3D) playback requires a mono buffer. Stereo assets keep a mono
+                // downmix around for exactly this case, instead of being rejected outright.
+                let positional_buffer = match &asset.mono_buffer {
+                    Some(mono_buffer) => mono_buffer,
+                    None => &asset.buffer,
+                };
+
                 let _ = source.set_reference_distance(0.0);
                 let _ = source.set_rolloff_factor(1.0);
                 let _ = source.set_min_gain(0.0);
+                let _ = source.set_buffer(Some(positional_buffer));
             }
         }
 
-        return_context(context);
-
         return Ok(SoundSource {
             emitter_type,
             source,
+            last_state: PlaybackState::Initial,
+            on_end: None,
         });
     }
 
@@ -56,6 +87,45 @@ impl SoundSource {
         let _ = self.source.play();
     }
 
+    /// Pauses playback. Use `play_sound()` to resume.
+    pub fn pause(&mut self) {
+        let _ = self.source.pause();
+    }
+
+    /// Stops playback.
+    pub fn stop(&mut self) {
+        let _ = self.source.stop();
+    }
+
+    /// Rewinds the source back to the start of its buffer and stops it.
+    pub fn rewind(&mut self) {
+        let _ = self.source.rewind();
+    }
+
+    /// Seeks to the given offset, in seconds, from the start of the buffer.
+    pub fn set_playback_position(&mut self, seconds: f32) {
+        let _ = self.source.set_sec_offset(seconds);
+    }
+
+    /// Returns the current playback offset, in seconds, from the start of the buffer.
+    pub fn playback_position(&self) -> f32 {
+        self.source.sec_offset().unwrap()
+    }
+
+    /// Returns the current playback state of the source.
+    pub fn state(&self) -> PlaybackState {
+        self.source.state().unwrap().into()
+    }
+
+    /// Registers a callback invoked when a non-looping source finishes playing.
+    ///
+    /// The callback is invoked from `update()` the first time it observes the source having
+    /// transitioned from Playing to Stopped, so `update()` must be called regularly for this
+    /// to fire. Capture whatever data the callback needs in the closure itself.
+    pub fn set_on_end<F: FnMut() + 'static>(&mut self, callback: F) {
+        self.on_end = Some(Box::new(callback));
+    }
+
     pub fn set_max_distance(&mut self, distance: f32) -> Result<(), SoundError> {
         match self.emitter_type {
             SoundSourceType::Simple => {
@@ -68,6 +138,54 @@ impl SoundSource {
         }
     }
 
+    /// Sets the distance at which the distance model stops attenuating (or starts, for the
+    /// clamped models) this source's volume.
+    pub fn set_reference_distance(&mut self, distance: f32) -> Result<(), SoundError> {
+        match self.emitter_type {
+            SoundSourceType::Simple => {
+                return Err(SoundError::WrongEmitterType);
+            }
+            SoundSourceType::Positional => {
+                let _ = self.source.set_reference_distance(distance);
+                return Ok(());
+            }
+        }
+    }
+
+    /// Sets how aggressively this source's volume falls off with distance.
+    pub fn set_rolloff_factor(&mut self, factor: f32) -> Result<(), SoundError> {
+        match self.emitter_type {
+            SoundSourceType::Simple => {
+                return Err(SoundError::WrongEmitterType);
+            }
+            SoundSourceType::Positional => {
+                let _ = self.source.set_rolloff_factor(factor);
+                return Ok(());
+            }
+        }
+    }
+
+    /// Changes the pitch multiplier of the source (1.0 = normal, 2.0 = one octave up, 0.5 = one
+    /// octave down).
+    pub fn set_pitch(&mut self, pitch: f32) {
+        let _ = self.source.set_pitch(pitch);
+    }
+
+    /// Returns the pitch multiplier of the source.
+    pub fn pitch(&self) -> Result<f32, AllenError> {
+        self.source.pitch()
+    }
+
+    /// Sets the source's gain (1.0 = unattenuated).
+    pub fn set_volume(&mut self, volume: f32) {
+        let _ = self.source.set_gain(volume);
+    }
+
+    /// Returns the source's gain.
+    pub fn volume(&self) -> Result<f32, AllenError> {
+        self.source.gain()
+    }
+
     pub fn get_max_distance(&mut self) -> Result<f32, SoundError> {
         match self.emitter_type {
             SoundSourceType::Simple => {
@@ -79,10 +197,43 @@ impl SoundSource {
 
     pub fn update(&mut self, sound_position: [f32; 3]) -> Result<(), SoundError> {
         let position_result_result = self.source.set_position(sound_position.into());
+
+        let current_state = self.state();
+        if self.last_state == PlaybackState::Playing
+            && current_state == PlaybackState::Stopped
+            && !self.is_looping()
+        {
+            if let Some(on_end) = &mut self.on_end {
+                on_end();
+            }
+        }
+        self.last_state = current_state;
+
         match position_result_result {
             Ok(()) => Ok(()),
             Err(error) => Err(SoundError::SettingPositionError(error)),
         }
     }
+
+    /// Routes this source's output through an EFX auxiliary effect slot (e.g. for reverb).
+    pub fn set_effect_slot(&mut self, al: &EzAl, slot: &efx::EffectSlot) -> Result<(), SoundError> {
+        self.set_aux_send(al, slot, None)
+    }
+
+    /// Routes this source's output into an auxiliary effect slot (e.g. for reverb), optionally
+    /// passing it through a filter first.
+    pub fn set_aux_send(&mut self, al: &EzAl, slot: &efx::EffectSlot, filter: Option<&efx::Filter>) -> Result<(), SoundError> {
+        let filter_id = match filter {
+            Some(filter) => filter.filter_id,
+            None => 0,
+        };
+
+        efx::route_to_aux_send(al, self.source.id(), slot.slot_id, filter_id)
+    }
+
+    /// Applies a direct (non-reverb) filter to this source, e.g. for occlusion.
+    pub fn set_direct_filter(&mut self, al: &EzAl, filter: &efx::DirectFilter) -> Result<(), SoundError> {
+        efx::apply_direct_filter(al, self.source.id(), filter.filter_id)
+    }
 }
 
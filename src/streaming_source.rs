@@ -0,0 +1,330 @@
+use std::fs::File;
+use std::io::BufReader;
+
+use allen::{Buffer, BufferData, Channels, Source};
+use claxon::FlacReader;
+use hound::WavReader;
+use lewton::inside_ogg::OggStreamReader;
+use minimp3::{Decoder as Mp3Decoder, Error as Mp3Error};
+
+use crate::{EzAl, SoundError};
+
+const STREAM_BUFFER_COUNT: usize = 4;
+const STREAM_CHUNK_SAMPLES: usize = 8192;
+
+enum StreamDecoder {
+    Wav(WavReader<BufReader<File>>),
+    Ogg(OggStreamReader<File>),
+    Flac(FlacReader<File>),
+    Mp3(Mp3Decoder<File>, Vec<i16>),
+}
+
+impl StreamDecoder {
+    fn open(path: &str) -> Result<(Self, Channels, i32), SoundError> {
+        let extension = std::path::Path::new(path)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_lowercase());
+
+        match extension.as_deref() {
+            Some("wav") => {
+                let reader = match WavReader::open(path) {
+                    Ok(reader) => reader,
+                    Err(_) => return Err(SoundError::SoundAssetLoadingError),
+                };
+
+                if reader.spec().channels > 2 {
+                    return Err(SoundError::TooManyChannelsError);
+                }
+
+                let channels = channels_from_count(reader.spec().channels);
+                let sample_rate = reader.spec().sample_rate as i32;
+                Ok((StreamDecoder::Wav(reader), channels, sample_rate))
+            }
+            Some("ogg") => {
+                let file = match File::open(path) {
+                    Ok(file) => file,
+                    Err(_) => return Err(SoundError::SoundAssetLoadingError),
+                };
+
+                let reader = match OggStreamReader::new(file) {
+                    Ok(reader) => reader,
+                    Err(_) => return Err(SoundError::SoundAssetLoadingError),
+                };
+
+                if reader.ident_hdr.audio_channels > 2 {
+                    return Err(SoundError::TooManyChannelsError);
+                }
+
+                let channels = channels_from_count(reader.ident_hdr.audio_channels as u16);
+                let sample_rate = reader.ident_hdr.audio_sample_rate as i32;
+                Ok((StreamDecoder::Ogg(reader), channels, sample_rate))
+            }
+            Some("flac") => {
+                let reader = match FlacReader::open(path) {
+                    Ok(reader) => reader,
+                    Err(_) => return Err(SoundError::SoundAssetLoadingError),
+                };
+
+                if reader.streaminfo().channels > 2 {
+                    return Err(SoundError::TooManyChannelsError);
+                }
+
+                let channels = channels_from_count(reader.streaminfo().channels as u16);
+                let sample_rate = reader.streaminfo().sample_rate as i32;
+                Ok((StreamDecoder::Flac(reader), channels, sample_rate))
+            }
+            Some("mp3") => {
+                let file = match File::open(path) {
+                    Ok(file) => file,
+                    Err(_) => return Err(SoundError::SoundAssetLoadingError),
+                };
+
+                let mut decoder = Mp3Decoder::new(file);
+                let frame = match decoder.next_frame() {
+                    Ok(frame) => frame,
+                    Err(_) => return Err(SoundError::SoundAssetLoadingError),
+                };
+
+                if frame.channels > 2 {
+                    return Err(SoundError::TooManyChannelsError);
+                }
+
+                let channels = channels_from_count(frame.channels as u16);
+                let sample_rate = frame.sample_rate;
+                // The first frame is already decoded (it had to be, to read channels/sample
+                // rate), so next_chunk() hands it out before decoding any further frames.
+                Ok((StreamDecoder::Mp3(decoder, frame.data), channels, sample_rate))
+            }
+            _ => Err(SoundError::SoundAssetLoadingError),
+        }
+    }
+
+    /// Returns about `max_samples` decoded samples, or an empty `Vec` at end of stream. WAV and
+    /// FLAC stop at exactly `max_samples`; OGG and MP3 decode whole packets/frames until at
+    /// least `max_samples` are collected, so they can return somewhat more.
+    fn next_chunk(&mut self, max_samples: usize) -> Result<Vec<i16>, SoundError> {
+        match self {
+            StreamDecoder::Wav(reader) => {
+                let samples: Vec<i16> = reader
+                    .samples::<i16>()
+                    .take(max_samples)
+                    .map(|s| s.unwrap())
+                    .collect();
+                Ok(samples)
+            }
+            StreamDecoder::Ogg(reader) => {
+                let mut samples = Vec::new();
+                while samples.len() < max_samples {
+                    match reader.read_dec_packet_itl() {
+                        Ok(Some(mut packet)) => samples.append(&mut packet),
+                        Ok(None) => break,
+                        Err(_) => return Err(SoundError::SoundAssetLoadingError),
+                    }
+                }
+                Ok(samples)
+            }
+            StreamDecoder::Flac(reader) => {
+                // claxon yields samples scaled to the file's actual bit depth, not always
+                // 16-bit, so rescale down to 16-bit instead of truncating.
+                let shift = reader.streaminfo().bits_per_sample.saturating_sub(16);
+                let mut samples = Vec::new();
+                for sample in reader.samples().take(max_samples) {
+                    match sample {
+                        Ok(sample) => samples.push((sample >> shift) as i16),
+                        Err(_) => return Err(SoundError::SoundAssetLoadingError),
+                    }
+                }
+                Ok(samples)
+            }
+            StreamDecoder::Mp3(decoder, pending) => {
+                let mut samples = Vec::new();
+                samples.append(pending);
+
+                while samples.len() < max_samples {
+                    match decoder.next_frame() {
+                        Ok(frame) => samples.extend_from_slice(&frame.data),
+                        Err(Mp3Error::Eof) => break,
+                        Err(_) => return Err(SoundError::SoundAssetLoadingError),
+                    }
+                }
+                Ok(samples)
+            }
+        }
+    }
+}
+
+fn channels_from_count(count: u16) -> Channels {
+    if count > 1 { Channels::Stereo } else { Channels::Mono }
+}
+
+/// A source that streams a file in small chunks instead of uploading it whole, for long
+/// music/voice tracks where an in-RAM `SoundAsset` would waste too much memory.
+pub struct StreamingSource {
+    source: Source,
+    decoder: StreamDecoder,
+    path: String,
+    channels: Channels,
+    sample_rate: i32,
+    looping: bool,
+    exhausted: bool,
+    buffers_processed: u32,
+    samples_buffered: usize,
+}
+
+impl StreamingSource {
+    pub fn new(al: &EzAl, path: &str) -> Result<Self, SoundError> {
+        let (mut decoder, channels, sample_rate) = match StreamDecoder::open(path) {
+            Ok(opened) => opened,
+            Err(err) => return Err(err),
+        };
+
+        let context = &al.context;
+        let source_result = context.new_source();
+        let source = match source_result {
+            Ok(source) => source,
+            Err(err) => {
+                return Err(SoundError::SourceCreationFailedError(err));
+            }
+        };
+        source.set_relative(true).unwrap();
+
+        let mut samples_buffered = 0;
+        for _ in 0..STREAM_BUFFER_COUNT {
+            let chunk = match decoder.next_chunk(STREAM_CHUNK_SAMPLES) {
+                Ok(chunk) => chunk,
+                Err(err) => {
+                    return Err(err);
+                }
+            };
+
+            if chunk.is_empty() {
+                break;
+            }
+
+            let buffer = match context.new_buffer() {
+                Ok(buffer) => buffer,
+                Err(err) => {
+                    return Err(SoundError::BufferCreationFailedError(err));
+                }
+            };
+
+            if let Err(err) = buffer.data(BufferData::I16(&chunk), channels, sample_rate) {
+                return Err(SoundError::BufferCreationFailedError(err));
+            }
+
+            if let Err(err) = source.queue_buffer(&buffer) {
+                return Err(SoundError::BufferCreationFailedError(err));
+            }
+
+            samples_buffered += chunk.len();
+        }
+
+        Ok(StreamingSource {
+            source,
+            decoder,
+            path: path.to_string(),
+            channels,
+            sample_rate,
+            looping: false,
+            exhausted: false,
+            buffers_processed: 0,
+            samples_buffered,
+        })
+    }
+
+    pub fn set_looping(&mut self, should_loop: bool) {
+        self.looping = should_loop;
+    }
+
+    pub fn is_looping(&self) -> bool {
+        self.looping
+    }
+
+    pub fn play_sound(&mut self) {
+        let _ = self.source.play();
+    }
+
+    /// Unqueues processed buffers, refills them with the next decoded chunk, and re-queues them.
+    /// Once a non-looping stream has reached end of file, processed buffers are unqueued and
+    /// dropped instead of being refilled.
+    /// Call this once per frame/tick while the source is playing.
+    pub fn update(&mut self, _al: &EzAl) -> Result<(), SoundError> {
+        let processed = match self.source.buffers_processed() {
+            Ok(processed) => processed,
+            Err(err) => {
+                return Err(SoundError::BufferCreationFailedError(err));
+            }
+        };
+
+        for _ in 0..processed {
+            let buffer = match self.source.unqueue_buffer() {
+                Ok(buffer) => buffer,
+                Err(err) => {
+                    return Err(SoundError::BufferCreationFailedError(err));
+                }
+            };
+
+            let mut chunk = match self.decoder.next_chunk(STREAM_CHUNK_SAMPLES) {
+                Ok(chunk) => chunk,
+                Err(err) => {
+                    return Err(err);
+                }
+            };
+
+            if chunk.is_empty() {
+                if !self.looping {
+                    self.exhausted = true;
+                    self.buffers_processed += 1;
+                    continue;
+                }
+
+                let (decoder, channels, sample_rate) = match StreamDecoder::open(&self.path) {
+                    Ok(opened) => opened,
+                    Err(err) => {
+                        return Err(err);
+                    }
+                };
+                self.decoder = decoder;
+                self.channels = channels;
+                self.sample_rate = sample_rate;
+
+                chunk = match self.decoder.next_chunk(STREAM_CHUNK_SAMPLES) {
+                    Ok(chunk) => chunk,
+                    Err(err) => {
+                        return Err(err);
+                    }
+                };
+            }
+
+            if let Err(err) = buffer.data(BufferData::I16(&chunk), self.channels, self.sample_rate) {
+                return Err(SoundError::BufferCreationFailedError(err));
+            }
+
+            if let Err(err) = self.source.queue_buffer(&buffer) {
+                return Err(SoundError::BufferCreationFailedError(err));
+            }
+
+            self.samples_buffered += chunk.len();
+            self.buffers_processed += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Total number of processed buffers unqueued over the lifetime of this source, including
+    /// those not refilled because a non-looping stream had ended.
+    pub fn buffers_processed(&self) -> u32 {
+        self.buffers_processed
+    }
+
+    /// Total number of samples ever uploaded into this source's buffers.
+    pub fn samples_buffered(&self) -> usize {
+        self.samples_buffered
+    }
+
+    /// `true` once a non-looping stream has reached end of file.
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+}